@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+/// A single connection lifecycle transition (`created`, `fused`, `closed`
+/// or `reaped`), published as one JSON record per event.
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    pub event: &'static str,
+    pub id: String,
+    pub idx: usize,
+    pub name: Option<String>,
+    pub bytes: u64,
+    pub reason: Option<String>,
+    pub ts: u64,
+}
+
+impl Event {
+    pub fn new(event: &'static str, id: impl Into<String>, idx: usize) -> Self {
+        Self {
+            event,
+            id: id.into(),
+            idx,
+            name: None,
+            bytes: 0,
+            reason: None,
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+
+    pub fn name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn bytes(mut self, bytes: u64) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// Mirrors a typical broker producer config: where to connect, which topic
+/// to publish on, how this process identifies itself, and how deep the
+/// fan-out buffer should be.
+#[derive(Debug, Clone)]
+pub struct EventsConfig {
+    pub brokers: String,
+    pub topic: String,
+    pub client_id: String,
+    pub buffer_size: usize,
+}
+
+/// Broker backend a [`Publisher`] pushes encoded records to; implemented
+/// once per supported broker so `Publisher` itself stays broker-agnostic.
+pub trait EventSink: Send + Sync + 'static {
+    fn publish(&self, payload: Vec<u8>) -> BoxFuture<'_, Result<()>>;
+}
+
+/// Fans connection lifecycle events out to an [`EventSink`] over a
+/// fire-and-forget channel: a full buffer or a broker hiccup just drops the
+/// event rather than ever stalling a transfer.
+#[derive(Clone)]
+pub struct Publisher {
+    tx: mpsc::Sender<Event>,
+}
+
+impl Publisher {
+    fn spawn(topic: String, sink: Arc<dyn EventSink>, buffer_size: usize) -> Self {
+        let (tx, mut rx) = mpsc::channel::<Event>(buffer_size);
+
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                let payload = match serde_json::to_vec(&event) {
+                    Ok(payload) => payload,
+                    Err(err) => {
+                        println!("[events] failed to encode {event:?}: {err}");
+                        continue;
+                    }
+                };
+
+                if let Err(err) = sink.publish(payload).await {
+                    println!(
+                        "[events:{topic}] failed to publish {}/{}: {err:#}",
+                        event.event, event.id
+                    );
+                }
+            }
+        });
+
+        Self { tx }
+    }
+
+    pub fn send(&self, event: Event) {
+        _ = self.tx.try_send(event);
+    }
+}
+
+/// Connects to whichever broker `config.brokers` points at (a `nats://` URL
+/// selects NATS, anything else is treated as a Kafka bootstrap server list)
+/// and returns a [`Publisher`] backed by it.
+pub async fn connect(config: EventsConfig) -> Result<Publisher> {
+    let sink: Arc<dyn EventSink> = if config.brokers.starts_with("nats://") {
+        Arc::new(NatsSink::connect(&config).await?)
+    } else {
+        Arc::new(KafkaSink::connect(&config)?)
+    };
+
+    Ok(Publisher::spawn(config.topic, sink, config.buffer_size))
+}
+
+struct KafkaSink {
+    producer: rdkafka::producer::FutureProducer,
+    topic: String,
+}
+
+impl KafkaSink {
+    fn connect(config: &EventsConfig) -> Result<Self> {
+        use rdkafka::config::ClientConfig;
+
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("client.id", &config.client_id)
+            .create()
+            .context("failed to create Kafka producer")?;
+
+        Ok(Self {
+            producer,
+            topic: config.topic.clone(),
+        })
+    }
+}
+
+impl EventSink for KafkaSink {
+    fn publish(&self, payload: Vec<u8>) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            use rdkafka::producer::FutureRecord;
+
+            self.producer
+                .send(
+                    FutureRecord::<(), _>::to(&self.topic).payload(&payload),
+                    Duration::from_secs(0),
+                )
+                .await
+                .map_err(|(err, _)| anyhow::anyhow!(err))?;
+
+            Ok(())
+        })
+    }
+}
+
+struct NatsSink {
+    client: async_nats::Client,
+    subject: String,
+}
+
+impl NatsSink {
+    async fn connect(config: &EventsConfig) -> Result<Self> {
+        let client = async_nats::connect(&config.brokers)
+            .await
+            .context("failed to connect to NATS")?;
+
+        Ok(Self {
+            client,
+            subject: config.topic.clone(),
+        })
+    }
+}
+
+impl EventSink for NatsSink {
+    fn publish(&self, payload: Vec<u8>) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            self.client
+                .publish(self.subject.clone(), payload.into())
+                .await
+                .context("failed to publish to NATS")?;
+
+            Ok(())
+        })
+    }
+}
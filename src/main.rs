@@ -1,24 +1,35 @@
 use anyhow::Result;
 use axum::body::{Body, BodyDataStream};
 use axum::extract::{Path, Query, State as AxumState};
-use axum::http::{header, StatusCode};
-use axum::response::Response;
-use axum::routing::get;
-use axum::{Error, Router};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get};
+use axum::{Error, Json, Router};
+use bytes::Bytes;
 use clap::Parser;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
 use futures::{stream, FutureExt, StreamExt};
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io;
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
 use tokio::net::TcpListener;
-use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex, Notify};
 use tokio::{task, time};
 use tokio_stream::wrappers::ReceiverStream;
 use ubyte::ByteUnit;
 
+mod events;
+mod spool;
+
+use events::{Event, Publisher as EventsPublisher};
+use spool::Spool;
+
 #[derive(Debug, Parser)]
 struct Args {
     #[clap(long)]
@@ -52,6 +63,34 @@ struct Args {
     #[arg(value_parser = Self::parse_duration)]
     chunk_timeout: Duration,
 
+    /// Quantile of observed inter-chunk latency used to derive the adaptive
+    /// chunk timeout; e.g. `0.9` for p90. Must be between 0.0 and 1.0.
+    #[clap(long)]
+    #[clap(default_value = "0.9")]
+    #[arg(value_parser = Self::parse_quantile)]
+    timeout_quantile: f64,
+
+    /// Factor applied to the observed quantile to get the effective chunk
+    /// timeout, giving slow-but-legitimate transfers some headroom. Must be
+    /// positive.
+    #[clap(long)]
+    #[clap(default_value = "3.0")]
+    #[arg(value_parser = Self::parse_multiplier)]
+    timeout_multiplier: f64,
+
+    /// Lower bound for the adaptive chunk timeout; `--chunk-timeout` is used
+    /// as-is until enough samples have been observed.
+    #[clap(long)]
+    #[clap(default_value = "1s")]
+    #[arg(value_parser = Self::parse_duration)]
+    min_chunk_timeout: Duration,
+
+    /// Upper bound for the adaptive chunk timeout.
+    #[clap(long)]
+    #[clap(default_value = "10m")]
+    #[arg(value_parser = Self::parse_duration)]
+    max_chunk_timeout: Duration,
+
     #[clap(long)]
     #[clap(default_value = "8GB")]
     #[arg(value_parser = Self::parse_storage)]
@@ -60,6 +99,46 @@ struct Args {
     #[clap(long)]
     #[clap(default_value = "1024")]
     max_connections: usize,
+
+    /// Enables store-and-forward mode: uploads are chunked to disk under
+    /// this directory instead of being relayed live, so the sender doesn't
+    /// have to stay connected until a receiver shows up.
+    #[clap(long)]
+    spool: Option<PathBuf>,
+
+    /// How long an unclaimed spool is kept on disk before the background
+    /// sweeper purges it; only relevant when `--spool` is set.
+    #[clap(long)]
+    #[clap(default_value = "24h")]
+    #[arg(value_parser = Self::parse_duration)]
+    spool_ttl: Duration,
+
+    /// Broker(s) to publish connection lifecycle events to; a `nats://` URL
+    /// connects over NATS, anything else is treated as a Kafka bootstrap
+    /// server list. Optional - when unset, lifecycle events are only logged
+    /// to stdout, same as before.
+    #[clap(long)]
+    events_brokers: Option<String>,
+
+    /// Topic (Kafka) or subject (NATS) lifecycle events are published under.
+    #[clap(long)]
+    #[clap(default_value = "skicka.connections")]
+    events_topic: String,
+
+    #[clap(long)]
+    #[clap(default_value = "skicka")]
+    events_client_id: String,
+
+    /// Depth of the fire-and-forget event buffer; once full, new events are
+    /// dropped rather than ever blocking a transfer.
+    #[clap(long)]
+    #[clap(default_value = "1024")]
+    events_buffer_size: usize,
+
+    /// Bearer token required on `/admin/*` routes; when unset, the admin API
+    /// is disabled entirely (requests get a 404, same as an unknown route).
+    #[clap(long)]
+    admin_token: Option<String>,
 }
 
 impl Args {
@@ -72,11 +151,51 @@ impl Args {
             .map(|u| u.as_u64())
             .map_err(|err| err.to_string())
     }
+
+    fn parse_quantile(arg: &str) -> Result<f64, String> {
+        let quantile: f64 = arg
+            .parse()
+            .map_err(|_| format!("invalid quantile: {arg}"))?;
+
+        if (0.0..=1.0).contains(&quantile) {
+            Ok(quantile)
+        } else {
+            Err(format!(
+                "quantile must be between 0.0 and 1.0, got {quantile}"
+            ))
+        }
+    }
+
+    fn parse_multiplier(arg: &str) -> Result<f64, String> {
+        let multiplier: f64 = arg
+            .parse()
+            .map_err(|_| format!("invalid multiplier: {arg}"))?;
+
+        if multiplier > 0.0 {
+            Ok(multiplier)
+        } else {
+            Err(format!("multiplier must be positive, got {multiplier}"))
+        }
+    }
+
+    /// Cross-field checks that a single `value_parser` can't express.
+    fn validate(&self) -> Result<()> {
+        if self.min_chunk_timeout > self.max_chunk_timeout {
+            anyhow::bail!(
+                "--min-chunk-timeout ({:?}) must not be greater than --max-chunk-timeout ({:?})",
+                self.min_chunk_timeout,
+                self.max_chunk_timeout,
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    args.validate()?;
 
     println!(r#"   _____ _    _      _         "#);
     println!(r#"  / ____| |  (_)    | |        "#);
@@ -90,15 +209,60 @@ async fn main() -> Result<()> {
 
     let listener = TcpListener::bind(&args.listen).await?;
 
+    let spool = match &args.spool {
+        Some(dir) => {
+            let spool = Spool::new(dir.clone());
+
+            task::spawn(spool.clone().run_sweeper(args.spool_ttl));
+
+            Some(spool)
+        }
+
+        None => None,
+    };
+
+    let events = match &args.events_brokers {
+        Some(brokers) => {
+            let config = events::EventsConfig {
+                brokers: brokers.clone(),
+                topic: args.events_topic.clone(),
+                client_id: args.events_client_id.clone(),
+                buffer_size: args.events_buffer_size,
+            };
+
+            // Lifecycle events are best-effort telemetry, same as the broker
+            // publishing itself further down the line - a broker that's down
+            // at startup shouldn't stop skicka from serving transfers.
+            match events::connect(config).await {
+                Ok(events) => Some(events),
+
+                Err(err) => {
+                    println!("[events] failed to connect, continuing without it: {err:#}");
+
+                    None
+                }
+            }
+        }
+
+        None => None,
+    };
+
     let state = Arc::new(State {
         args,
         conns: Default::default(),
+        conn_count: Default::default(),
         next_conn_idx: Default::default(),
+        timeouts: Default::default(),
+        spool,
+        events,
+        admin: Default::default(),
     });
 
     let app = Router::new()
         .route("/", get(handle_index).put(handle_send).post(handle_send))
         .route("/:id", get(handle_recv))
+        .route("/admin/conns", get(handle_admin_list))
+        .route("/admin/conns/:id", delete(handle_admin_delete))
         .with_state(state);
 
     axum::serve(listener, app).await?;
@@ -108,15 +272,128 @@ async fn main() -> Result<()> {
 
 struct State {
     args: Args,
-    conns: Mutex<HashMap<String, Conn>>,
+
+    /// Sharded in place of a single `Mutex<HashMap<_>>` so that the
+    /// `handle_send`/`handle_recv`/reaper hot path doesn't serialize on one
+    /// lock as thousands of short transfers churn through the relay.
+    ///
+    /// Each entry is additionally wrapped in a `std::sync::Mutex` since
+    /// `Conn::body` is `!Sync` (axum's body streams are `Send` but not
+    /// `Sync`), and `DashMap<K, V>` itself is only `Sync` when `V: Sync` -
+    /// the per-entry mutex gives the map that marker back without needing a
+    /// lock held across an `.await`.
+    conns: DashMap<String, StdMutex<Conn>>,
+
+    /// Live connection count, checked against `--max-connections`; tracked
+    /// separately from `conns.len()` so the overload check doesn't have to
+    /// walk every shard.
+    conn_count: AtomicUsize,
+
     next_conn_idx: AtomicUsize,
+    timeouts: Mutex<TimeoutManager>,
+
+    /// Present only when `--spool` is set; routes uploads through on-disk
+    /// store-and-forward instead of the live in-memory relay.
+    spool: Option<Spool>,
+
+    /// Present only when `--events-brokers` is set; mirrors the
+    /// created/fused/closed/reaped lifecycle log lines to a broker.
+    events: Option<EventsPublisher>,
+
+    /// Admin-visible bookkeeping, one entry per live connection. Kept
+    /// separate from `conns` since it must stay reachable after a connection
+    /// is fused and removed from `conns` to back `run_producer`.
+    admin: DashMap<String, Arc<ConnHandle>>,
+}
+
+/// Bytes transferred and abort state for a connection, shared between its
+/// `Conn` and its `ConnHandle` so an admin abort (or byte count) reaches the
+/// producer loop without a second lookup.
+struct ConnHandle {
+    idx: usize,
+    name: Option<String>,
+    created_at: Instant,
+    bytes: Arc<AtomicU64>,
+    aborted: Arc<AtomicBool>,
+
+    /// Notified by `DELETE /admin/conns/:id` so a producer blocked inside a
+    /// single `body.next()` call unwinds immediately, instead of only being
+    /// noticed once that call's timeout elapses on its own.
+    abort_notify: Arc<Notify>,
+}
+
+/// Learns an adaptive chunk timeout from observed inter-chunk latencies,
+/// so that bursty-but-legitimate clients don't need a globally raised
+/// `--chunk-timeout`.
+#[derive(Default)]
+struct TimeoutManager {
+    samples: VecDeque<Duration>,
 }
 
+impl TimeoutManager {
+    /// Below this many samples, [`Self::estimate`] falls back to the
+    /// configured `--chunk-timeout` instead of trusting the quantile.
+    const WARMUP_SAMPLES: usize = 8;
+
+    /// Number of most-recent inter-chunk latencies kept around.
+    const MAX_SAMPLES: usize = 256;
+
+    fn record(&mut self, latency: Duration) {
+        if self.samples.len() >= Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(latency);
+    }
+
+    fn estimate(&self, args: &Args) -> Duration {
+        if self.samples.len() < Self::WARMUP_SAMPLES {
+            return args.chunk_timeout;
+        }
+
+        let mut samples: Vec<_> = self.samples.iter().copied().collect();
+        samples.sort_unstable();
+
+        let idx = (((samples.len() - 1) as f64) * args.timeout_quantile).round() as usize;
+        let quantile = samples[idx];
+
+        quantile
+            .mul_f64(args.timeout_multiplier)
+            .clamp(args.min_chunk_timeout, args.max_chunk_timeout)
+    }
+}
+
+/// Capacity of a [`Conn`]'s fan-out channel; chunks older than this (counted
+/// per slow receiver) are dropped, turning that receiver's `recv()` into a
+/// `Lagged` error instead of blocking the rest of the fan-out.
+const BROADCAST_CAPACITY: usize = 16;
+
+/// A chunk as forwarded through a [`Conn`]'s broadcast channel. The error
+/// side can't carry `axum::Error` directly since it isn't `Clone`, so it's
+/// downgraded to a message and re-wrapped per receiver.
+type ChunkResult = Result<Bytes, Arc<str>>;
+
 struct Conn {
     idx: usize,
     name: Option<String>,
     body: BodyDataStream,
     on_completed: oneshot::Sender<()>,
+
+    /// Fan-out channel the producer publishes chunks on; every `GET /:id`
+    /// subscribes to this instead of draining `body` itself.
+    broadcast: broadcast::Sender<ChunkResult>,
+
+    /// Number of receivers the producer waits for before it starts pulling
+    /// from `body`; taken from `POST /?receivers=N` (defaults to 1).
+    receivers_expected: usize,
+    receivers_joined: usize,
+
+    /// Shared with this connection's `ConnHandle` in `State::admin`, so
+    /// `/admin/conns` can read live progress and `DELETE /admin/conns/:id`
+    /// can signal the producer loop to stop.
+    bytes: Arc<AtomicU64>,
+    aborted: Arc<AtomicBool>,
+    abort_notify: Arc<Notify>,
 }
 
 async fn handle_index(state: AxumState<Arc<State>>) -> String {
@@ -126,6 +403,12 @@ async fn handle_index(state: AxumState<Arc<State>>) -> String {
 #[derive(Debug, Deserialize)]
 struct SendQuery {
     name: Option<String>,
+
+    /// Number of concurrent `GET /:id` receivers to fan this upload out to.
+    ///
+    /// The upload doesn't start streaming until all of them have connected,
+    /// since chunks aren't buffered for late joiners.
+    receivers: Option<usize>,
 }
 
 async fn handle_send(
@@ -133,14 +416,38 @@ async fn handle_send(
     query: Query<SendQuery>,
     body: Body,
 ) -> Result<Response, Response> {
-    let mut conns = state.conns.lock().await;
+    if let Some(spool) = state.spool.clone() {
+        return handle_send_spool(state, spool, query.0.name, body).await;
+    }
 
-    if conns.len() >= state.args.max_connections {
+    if state.conn_count.load(Ordering::Relaxed) >= state.args.max_connections {
         println!("[-] connection rejected (too many active connections)");
 
         return Ok(err_server_overloaded());
     }
 
+    let idx = state.next_conn_idx.fetch_add(1, Ordering::Relaxed);
+    let (on_completed_tx, on_completed_rx) = oneshot::channel();
+    let (broadcast_tx, _) = broadcast::channel(BROADCAST_CAPACITY);
+    let receivers_expected = query.0.receivers.unwrap_or(1).max(1);
+    let name = query.0.name;
+    let bytes = Arc::new(AtomicU64::new(0));
+    let aborted = Arc::new(AtomicBool::new(false));
+    let abort_notify = Arc::new(Notify::new());
+
+    let mut conn = Some(Conn {
+        idx,
+        name: name.clone(),
+        body: body.into_data_stream(),
+        on_completed: on_completed_tx,
+        broadcast: broadcast_tx,
+        receivers_expected,
+        receivers_joined: 0,
+        bytes: bytes.clone(),
+        aborted: aborted.clone(),
+        abort_notify: abort_notify.clone(),
+    });
+
     let id = {
         let mut tries = 0;
 
@@ -155,30 +462,41 @@ async fn handle_send(
 
             let id = names::Generator::default().next().unwrap();
 
-            if !conns.contains_key(&id) {
-                break id;
+            match state.conns.entry(id.clone()) {
+                Entry::Occupied(_) => continue,
+                Entry::Vacant(entry) => {
+                    entry.insert(StdMutex::new(conn.take().unwrap()));
+
+                    state.admin.insert(
+                        id.clone(),
+                        Arc::new(ConnHandle {
+                            idx,
+                            name: name.clone(),
+                            created_at: Instant::now(),
+                            bytes: bytes.clone(),
+                            aborted: aborted.clone(),
+                            abort_notify: abort_notify.clone(),
+                        }),
+                    );
+
+                    break id;
+                }
             }
         }
     };
 
-    let idx = state.next_conn_idx.fetch_add(1, Ordering::Relaxed);
-    let (on_completed_tx, on_completed_rx) = oneshot::channel();
-
-    conns.insert(
-        id.clone(),
-        Conn {
-            idx,
-            name: query.0.name,
-            body: body.into_data_stream(),
-            on_completed: on_completed_tx,
-        },
-    );
+    let active_connections = state.conn_count.fetch_add(1, Ordering::Relaxed) + 1;
 
     println!(
-        "[{idx}:{id}] connection created; active connections: {}",
-        conns.len(),
+        "[{idx}:{id}] connection created (expecting {receivers_expected} receiver{}); \
+         active connections: {active_connections}",
+        if receivers_expected == 1 { "" } else { "s" },
     );
 
+    if let Some(events) = &state.events {
+        events.send(Event::new("created", id.clone(), idx).name(name));
+    }
+
     let response = Body::from_stream({
         let response = if let Some(remote) = &state.args.remote {
             format!("{}/{}", remote, id)
@@ -196,16 +514,21 @@ async fn handle_send(
         async move {
             time::sleep(state.args.intent_timeout).await;
 
-            let mut conns = state.conns.lock().await;
+            if state
+                .conns
+                .remove_if(&id, |_, conn| conn.lock().unwrap().idx == idx)
+                .is_some()
+            {
+                state.admin.remove(&id);
 
-            if let Some(conn) = conns.get(&id) {
-                if conn.idx == idx {
-                    conns.remove(&id);
+                let active_connections = state.conn_count.fetch_sub(1, Ordering::Relaxed) - 1;
 
-                    println!(
-                        "[{idx}:{id}] connection reaped; active connections: {}",
-                        conns.len()
-                    );
+                println!(
+                    "[{idx}:{id}] connection reaped; active connections: {active_connections}"
+                );
+
+                if let Some(events) = &state.events {
+                    events.send(Event::new("reaped", id.clone(), idx));
                 }
             }
         }
@@ -214,22 +537,140 @@ async fn handle_send(
     Ok(Response::new(response))
 }
 
+/// `--spool` variant of [`handle_send`]: writes the whole body to disk
+/// before responding, instead of parking the sender until a receiver shows
+/// up.
+async fn handle_send_spool(
+    state: AxumState<Arc<State>>,
+    spool: Spool,
+    name: Option<String>,
+    body: Body,
+) -> Result<Response, Response> {
+    let id = {
+        let mut tries = 0;
+
+        loop {
+            tries += 1;
+
+            if tries >= 64 {
+                println!("[-] connection rejected (failed to generate name)");
+
+                return Ok(err_server_overloaded());
+            }
+
+            let id = names::Generator::default().next().unwrap();
+
+            if state.conns.contains_key(&id) {
+                continue;
+            }
+
+            // `reserve` creates the spool dir, so this is an atomic
+            // claim rather than the check-then-act race a bare
+            // existence check would be against a concurrent uploader
+            // that generated the same id.
+            match spool.reserve(&id).await {
+                Ok(true) => break id,
+                Ok(false) => continue,
+
+                Err(err) => {
+                    println!("[{id}] failed to reserve spool dir: {err}");
+
+                    return Ok(err_server_overloaded());
+                }
+            }
+        }
+    };
+
+    let size = match spool
+        .store(
+            &id,
+            name,
+            body.into_data_stream(),
+            state.args.max_transfer_size,
+        )
+        .await
+    {
+        Ok(size) => size,
+
+        Err(err) => {
+            println!("[{id}] spooling failed: {err:#}");
+
+            return Ok(err_server_overloaded());
+        }
+    };
+
+    println!("[{id}] spooled {} to disk", ByteUnit::Byte(size));
+
+    let response = if let Some(remote) = &state.args.remote {
+        format!("{}/{}\r\n", remote, id)
+    } else {
+        format!("{id}\r\n")
+    };
+
+    Ok(Response::new(Body::from(response)))
+}
+
 async fn handle_recv(
     state: AxumState<Arc<State>>,
     Path(id): Path<String>,
 ) -> Result<Response, Response> {
-    let Some(conn) = state.conns.lock().await.remove(&id) else {
-        return Ok(err_not_found("no such connection found\r\n"));
+    let (idx, name, broadcast, ready) = {
+        let Some(entry) = state.conns.get(&id) else {
+            if let Some(spool) = state.spool.clone() {
+                if let Some(info) = spool.info(&id).await {
+                    return Ok(handle_recv_spool(spool, id, info));
+                }
+            }
+
+            return Ok(err_not_found("no such connection found\r\n"));
+        };
+
+        let mut conn = entry.lock().unwrap();
+
+        conn.receivers_joined += 1;
+
+        (
+            conn.idx,
+            conn.name.clone(),
+            conn.broadcast.clone(),
+            conn.receivers_joined >= conn.receivers_expected,
+        )
     };
 
-    let Conn {
-        idx,
-        name,
-        mut body,
-        on_completed,
-    } = conn;
+    // Only the last receiver to join actually removes the connection and
+    // kicks off the producer; earlier ones just subscribe and wait. The
+    // lookup above must release its shard guard first, or this deadlocks.
+    let producer = if ready {
+        let conn = state
+            .conns
+            .remove(&id)
+            .map(|(_, conn)| conn.into_inner().unwrap());
+
+        if conn.is_some() {
+            state.conn_count.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        conn
+    } else {
+        None
+    };
+
+    println!("[{idx}:{id}] receiver joined");
+
+    // Subscribe before spawning the producer: otherwise its first
+    // `broadcast.send` can race ahead of this receiver's `subscribe` on
+    // another thread and find zero receivers, tearing down the transfer.
+    let mut broadcast_rx = broadcast.subscribe();
+
+    if let Some(conn) = producer {
+        println!("[{idx}:{id}] connection fused");
+
+        if let Some(events) = &state.events {
+            events.send(Event::new("fused", id.clone(), idx).name(name.clone()));
+        }
 
-    println!("[{idx}:{id}] connection fused");
+        task::spawn(run_producer(state.clone(), id.clone(), conn));
+    }
 
     let (stream_tx, stream_rx) = mpsc::channel(1);
 
@@ -237,19 +678,19 @@ async fn handle_recv(
         let mut size = 0;
 
         let reason = loop {
-            let chunk = time::timeout(state.args.chunk_timeout, body.next()).await;
+            let timeout = state.timeouts.lock().await.estimate(&state.args);
 
-            match chunk {
-                Ok(Some(chunk)) => {
+            match time::timeout(timeout, broadcast_rx.recv()).await {
+                Ok(Ok(chunk)) => {
                     if let Ok(chunk) = &chunk {
                         size += chunk.len() as u64;
-
-                        if size >= state.args.max_transfer_size {
-                            break "reached transfer size limit";
-                        }
                     }
 
-                    match time::timeout(state.args.chunk_timeout, stream_tx.send(chunk)).await {
+                    let chunk = chunk.map_err(|err| {
+                        Error::new(io::Error::new(io::ErrorKind::Other, err.to_string()))
+                    });
+
+                    match time::timeout(timeout, stream_tx.send(chunk)).await {
                         Ok(Ok(_)) => {
                             continue;
                         }
@@ -264,23 +705,23 @@ async fn handle_recv(
                     }
                 }
 
-                Ok(None) => {
+                Ok(Err(broadcast::error::RecvError::Lagged(_))) => {
+                    break "receiver lagged behind the producer, aborting";
+                }
+
+                Ok(Err(broadcast::error::RecvError::Closed)) => {
                     break "transfer completed";
                 }
 
                 Err(_) => {
-                    break "timed-out retrieving the next chunk";
+                    break "timed-out waiting for the next chunk";
                 }
             }
         };
 
-        _ = on_completed.send(());
-
         println!(
-            "[{idx}:{id}] connection closed after {}: {reason}; \
-             active connections: {}",
+            "[{idx}:{id}] receiver closed after {}: {reason}",
             ByteUnit::Byte(size),
-            state.conns.lock().await.len(),
         );
     });
 
@@ -300,6 +741,252 @@ async fn handle_recv(
     Ok(response)
 }
 
+/// `--spool` variant of [`handle_recv`]: streams the chunks already sitting
+/// on disk back to the client, deleting them once delivery finishes.
+fn handle_recv_spool(spool: Spool, id: String, info: spool::SpoolInfo) -> Response {
+    let (stream_tx, stream_rx) = mpsc::channel(1);
+
+    task::spawn(async move {
+        let mut size = 0;
+
+        for chunk_idx in 0..info.chunks {
+            match spool.read_chunk(&id, chunk_idx).await {
+                Ok(chunk) => {
+                    size += chunk.len() as u64;
+
+                    if stream_tx.send(Ok::<_, Error>(chunk)).await.is_err() {
+                        break;
+                    }
+                }
+
+                Err(err) => {
+                    println!("[{id}] spool read failed at chunk {chunk_idx}: {err}");
+
+                    break;
+                }
+            }
+        }
+
+        spool.remove(&id).await;
+
+        println!(
+            "[{id}] spooled connection closed after {}; removed from disk",
+            ByteUnit::Byte(size),
+        );
+    });
+
+    let mut response = Response::builder();
+
+    if let Some(file_name) = info.name {
+        response = response.header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        );
+    }
+
+    response
+        .body(Body::from_stream(ReceiverStream::new(stream_rx)))
+        .unwrap()
+}
+
+/// Drains `conn.body` and publishes each chunk to `conn.broadcast`, once all
+/// of the connection's expected receivers have subscribed. Runs exactly once
+/// per upload, regardless of how many receivers are attached.
+async fn run_producer(state: AxumState<Arc<State>>, id: String, conn: Conn) {
+    let Conn {
+        idx,
+        name,
+        mut body,
+        on_completed,
+        broadcast,
+        bytes,
+        aborted,
+        abort_notify,
+        ..
+    } = conn;
+
+    let mut size = 0;
+    let mut last_chunk_at: Option<Instant> = None;
+
+    let reason = loop {
+        if aborted.load(Ordering::Relaxed) {
+            break "aborted by operator";
+        }
+
+        let timeout = state.timeouts.lock().await.estimate(&state.args);
+
+        // Racing the read against `abort_notify` (rather than only checking
+        // `aborted` between iterations) lets an operator reclaim a producer
+        // that's stuck inside a single `body.next()` call, instead of
+        // waiting for that call's own timeout to elapse.
+        let chunk = tokio::select! {
+            biased;
+            _ = abort_notify.notified() => break "aborted by operator",
+            chunk = time::timeout(timeout, body.next()) => chunk,
+        };
+
+        match chunk {
+            Ok(Some(chunk)) => {
+                let now = Instant::now();
+
+                if let Some(prev) = last_chunk_at {
+                    state.timeouts.lock().await.record(now - prev);
+                }
+
+                last_chunk_at = Some(now);
+
+                if let Ok(chunk) = &chunk {
+                    size += chunk.len() as u64;
+                    bytes.store(size, Ordering::Relaxed);
+
+                    if size >= state.args.max_transfer_size {
+                        break "reached transfer size limit";
+                    }
+                }
+
+                let chunk: ChunkResult = chunk.map_err(|err| Arc::from(err.to_string()));
+
+                if broadcast.send(chunk).is_err() {
+                    break "all receivers disconnected";
+                }
+            }
+
+            Ok(None) => {
+                break "transfer completed";
+            }
+
+            Err(_) => {
+                break "timed-out retrieving the next chunk";
+            }
+        }
+    };
+
+    _ = on_completed.send(());
+    state.admin.remove(&id);
+
+    println!(
+        "[{idx}:{id}] connection closed after {}: {reason}; \
+         active connections: {}",
+        ByteUnit::Byte(size),
+        state.conn_count.load(Ordering::Relaxed),
+    );
+
+    if let Some(events) = &state.events {
+        events.send(
+            Event::new("closed", id.clone(), idx)
+                .name(name)
+                .bytes(size)
+                .reason(reason),
+        );
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AdminConn {
+    id: String,
+    idx: usize,
+    name: Option<String>,
+    bytes: u64,
+    age_secs: u64,
+}
+
+/// Checks the `Authorization: Bearer <token>` header against `--admin-token`.
+/// Returns 404 (not 401) when the flag isn't set at all, so the admin API is
+/// indistinguishable from a route that doesn't exist.
+fn check_admin_token(state: &State, headers: &HeaderMap) -> Result<(), Response> {
+    let Some(expected) = &state.args.admin_token else {
+        return Err(err_not_found(None::<&str>));
+    };
+
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    if provided == Some(expected.as_str()) {
+        Ok(())
+    } else {
+        Err(Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .unwrap())
+    }
+}
+
+async fn handle_admin_list(
+    state: AxumState<Arc<State>>,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    check_admin_token(&state, &headers)?;
+
+    let conns: Vec<AdminConn> = state
+        .admin
+        .iter()
+        .map(|entry| {
+            let handle = entry.value();
+
+            AdminConn {
+                id: entry.key().clone(),
+                idx: handle.idx,
+                name: handle.name.clone(),
+                bytes: handle.bytes.load(Ordering::Relaxed),
+                age_secs: handle.created_at.elapsed().as_secs(),
+            }
+        })
+        .collect();
+
+    Ok(Json(conns).into_response())
+}
+
+async fn handle_admin_delete(
+    state: AxumState<Arc<State>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Response, Response> {
+    check_admin_token(&state, &headers)?;
+
+    let Some(handle) = state.admin.get(&id).map(|entry| Arc::clone(entry.value())) else {
+        return Ok(err_not_found("no such connection found\r\n"));
+    };
+
+    handle.aborted.store(true, Ordering::Relaxed);
+    handle.abort_notify.notify_one();
+
+    // A connection still waiting for receivers hasn't started `run_producer`
+    // yet, so there's no streaming loop to notice the flag or notification;
+    // reap it here directly instead. A fused connection's producer is woken
+    // by `abort_notify` even if it's stuck inside a single `body.next()`
+    // call, so it unwinds (and cleans itself up) on its own.
+    if state
+        .conns
+        .remove_if(&id, |_, conn| conn.lock().unwrap().idx == handle.idx)
+        .is_some()
+    {
+        state.conn_count.fetch_sub(1, Ordering::Relaxed);
+        state.admin.remove(&id);
+
+        println!("[{}:{id}] connection aborted by operator", handle.idx);
+
+        if let Some(events) = &state.events {
+            events.send(
+                Event::new("closed", id.clone(), handle.idx)
+                    .name(handle.name.clone())
+                    .reason("aborted by operator"),
+            );
+        }
+    } else {
+        println!(
+            "[{}:{id}] connection marked for abort by operator",
+            handle.idx
+        );
+    }
+
+    Ok(Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .unwrap())
+}
+
 fn err_not_found(body: impl Into<Option<&'static str>>) -> Response<Body> {
     let body = body.into().map(Body::from).unwrap_or_default();
 
@@ -0,0 +1,240 @@
+use anyhow::{Context, Result};
+use axum::body::BodyDataStream;
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::fs;
+use ubyte::ByteUnit;
+
+/// Size of each chunk written to (and read back from) disk.
+const CHUNK_SIZE: usize = 128 * 1024;
+
+/// How often the sweeper wakes up to look for spools past their TTL; the TTL
+/// itself is configured separately via `--spool-ttl`.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Store-and-forward backend for `--spool`: instead of relaying a body live
+/// between an overlapping sender and receiver, uploads are chunked to disk
+/// under `dir` and streamed back out whenever a receiver eventually shows up.
+#[derive(Debug, Clone)]
+pub struct Spool {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Meta {
+    name: Option<String>,
+    size: u64,
+    chunks: usize,
+}
+
+/// Metadata read back for a spooled upload, enough to build the response
+/// (file name, chunk count) without touching the chunks themselves.
+pub struct SpoolInfo {
+    pub name: Option<String>,
+    pub chunks: usize,
+}
+
+impl Spool {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn conn_dir(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+
+    fn meta_path(&self, id: &str) -> PathBuf {
+        self.conn_dir(id).join("meta.json")
+    }
+
+    fn chunk_path(&self, id: &str, idx: usize) -> PathBuf {
+        self.conn_dir(id).join(format!("{idx:010}.chunk"))
+    }
+
+    /// Stamped by [`Self::reserve`] at claim time, independent of
+    /// `meta.json` (which only exists once a spool has fully landed), so the
+    /// sweeper can age out incomplete/orphaned spools too.
+    fn created_at_path(&self, id: &str) -> PathBuf {
+        self.conn_dir(id).join("created_at")
+    }
+
+    /// Atomically claims `id` for a new spool by creating its directory.
+    /// Returns `Ok(false)` instead of an error when it's already taken, so
+    /// the caller can just retry with a fresh id rather than racing another
+    /// uploader that generated the same one.
+    pub async fn reserve(&self, id: &str) -> io::Result<bool> {
+        fs::create_dir_all(&self.dir).await?;
+
+        match fs::create_dir(self.conn_dir(id)).await {
+            Ok(()) => {
+                let created_at = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+
+                fs::write(self.created_at_path(id), created_at.to_string()).await?;
+
+                Ok(true)
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Drains `body` into fixed-size chunks under `dir/<id>`, writing a
+    /// metadata record once every chunk has landed. Returns the total
+    /// transfer size, or an error if the body exceeds `max_size` or any
+    /// chunk fails to read or write; either way the partial spool dir is
+    /// removed, since `store` is the sole owner of whatever `reserve`
+    /// claimed. Assumes `id` was already claimed via [`Self::reserve`].
+    pub async fn store(
+        &self,
+        id: &str,
+        name: Option<String>,
+        body: BodyDataStream,
+        max_size: u64,
+    ) -> Result<u64> {
+        match self.store_inner(id, name, body, max_size).await {
+            Ok(size) => Ok(size),
+            Err(err) => {
+                self.remove(id).await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn store_inner(
+        &self,
+        id: &str,
+        name: Option<String>,
+        mut body: BodyDataStream,
+        max_size: u64,
+    ) -> Result<u64> {
+        let mut size = 0u64;
+        let mut chunk_idx = 0usize;
+        let mut buf = BytesMut::new();
+
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.context("failed to read request body")?;
+
+            size += chunk.len() as u64;
+
+            if size > max_size {
+                anyhow::bail!("spooled upload of {id} exceeded the {max_size}-byte transfer limit");
+            }
+
+            buf.extend_from_slice(&chunk);
+
+            while buf.len() >= CHUNK_SIZE {
+                let piece = buf.split_to(CHUNK_SIZE);
+
+                fs::write(self.chunk_path(id, chunk_idx), &piece)
+                    .await
+                    .with_context(|| format!("failed to write chunk {chunk_idx} for {id}"))?;
+
+                chunk_idx += 1;
+            }
+        }
+
+        if !buf.is_empty() {
+            fs::write(self.chunk_path(id, chunk_idx), &buf)
+                .await
+                .with_context(|| format!("failed to write chunk {chunk_idx} for {id}"))?;
+
+            chunk_idx += 1;
+        }
+
+        let meta = Meta {
+            name,
+            size,
+            chunks: chunk_idx,
+        };
+
+        fs::write(self.meta_path(id), serde_json::to_vec(&meta)?)
+            .await
+            .with_context(|| format!("failed to write spool metadata for {id}"))?;
+
+        Ok(size)
+    }
+
+    pub async fn info(&self, id: &str) -> Option<SpoolInfo> {
+        let meta = fs::read(self.meta_path(id)).await.ok()?;
+        let meta: Meta = serde_json::from_slice(&meta).ok()?;
+
+        Some(SpoolInfo {
+            name: meta.name,
+            chunks: meta.chunks,
+        })
+    }
+
+    pub async fn read_chunk(&self, id: &str, idx: usize) -> io::Result<Bytes> {
+        fs::read(self.chunk_path(id, idx)).await.map(Bytes::from)
+    }
+
+    pub async fn remove(&self, id: &str) {
+        _ = fs::remove_dir_all(self.conn_dir(id)).await;
+    }
+
+    /// Periodically purges spools whose sender never found a receiver,
+    /// meant to be run as a long-lived background task.
+    pub async fn run_sweeper(self, ttl: Duration) {
+        loop {
+            tokio::time::sleep(SWEEP_INTERVAL).await;
+            self.sweep(ttl).await;
+        }
+    }
+
+    async fn sweep(&self, ttl: Duration) {
+        let Ok(mut entries) = fs::read_dir(&self.dir).await else {
+            return;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let id = entry.file_name().to_string_lossy().into_owned();
+
+            // `created_at` is stamped by `reserve` regardless of whether the
+            // spool ever finished, so an interrupted upload (no `meta.json`)
+            // still ages out instead of being skipped forever.
+            let Some(created_at) = self.load_created_at(&id).await else {
+                continue;
+            };
+
+            let age = SystemTime::now()
+                .duration_since(created_at)
+                .unwrap_or_default();
+
+            if age < ttl {
+                continue;
+            }
+
+            let size = match self.load_meta(&id).await {
+                Some(meta) => ByteUnit::Byte(meta.size),
+                None => ByteUnit::Byte(0),
+            };
+
+            self.remove(&id).await;
+
+            println!(
+                "[{id}] spool swept after {:?} unclaimed ({size} on disk)",
+                age
+            );
+        }
+    }
+
+    async fn load_meta(&self, id: &str) -> Option<Meta> {
+        let meta = fs::read(self.meta_path(id)).await.ok()?;
+
+        serde_json::from_slice(&meta).ok()
+    }
+
+    async fn load_created_at(&self, id: &str) -> Option<SystemTime> {
+        let raw = fs::read_to_string(self.created_at_path(id)).await.ok()?;
+        let secs = raw.trim().parse().ok()?;
+
+        Some(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}